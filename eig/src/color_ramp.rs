@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::constants::RAINBOW;
+
+/// A single color stop in a [`ColorRamp`]: `position` is in `0.0..=1.0`.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorStop {
+    pub position: f32,
+    pub rgb: [f32; 3],
+}
+
+/// An ordered list of color stops that can be sampled at any `t` in
+/// `0.0..=1.0`, replacing the fixed 12-entry `RAINBOW` lookup with a smooth,
+/// arbitrary-length gradient.
+#[derive(Clone)]
+pub struct ColorRamp {
+    stops: Vec<ColorStop>,
+}
+
+impl ColorRamp {
+    /// Builds a ramp from stops, sorting them by position so callers may
+    /// pass stops in any order. Returns `None` if `stops` is empty.
+    pub fn new(mut stops: Vec<ColorStop>) -> Option<ColorRamp> {
+        if stops.is_empty() {
+            return None;
+        }
+        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(Ordering::Equal));
+        Some(ColorRamp { stops })
+    }
+
+    /// The existing `RAINBOW` constant, spread evenly across `0.0..=1.0`.
+    pub fn default_ramp() -> ColorRamp {
+        let count = RAINBOW.len();
+        let stops = RAINBOW
+            .iter()
+            .enumerate()
+            .map(|(index, rgb)| ColorStop {
+                position: index as f32 / (count - 1) as f32,
+                rgb: *rgb,
+            })
+            .collect();
+        ColorRamp::new(stops).expect("RAINBOW is non-empty")
+    }
+
+    /// Samples the ramp at `t`, clamping to the first/last stop outside
+    /// `0.0..=1.0` and linearly interpolating in linear-RGB space between the
+    /// bracketing stops otherwise.
+    pub fn sample(&self, t: f32) -> [f32; 3] {
+        let t = t.clamp(0.0, 1.0);
+        if let [only] = self.stops.as_slice() {
+            return only.rgb;
+        }
+        if t <= self.stops[0].position {
+            return self.stops[0].rgb;
+        }
+        let last = self.stops.len() - 1;
+        if t >= self.stops[last].position {
+            return self.stops[last].rgb;
+        }
+        let upper = self
+            .stops
+            .iter()
+            .position(|stop| stop.position >= t)
+            .unwrap_or(last);
+        let lower = upper.saturating_sub(1);
+        let (a, b) = (self.stops[lower], self.stops[upper]);
+        let span = b.position - a.position;
+        let mix = if span > 0.0 { (t - a.position) / span } else { 0.0 };
+        [
+            a.rgb[0] + (b.rgb[0] - a.rgb[0]) * mix,
+            a.rgb[1] + (b.rgb[1] - a.rgb[1]) * mix,
+            a.rgb[2] + (b.rgb[2] - a.rgb[2]) * mix,
+        ]
+    }
+}
+
+/// Maps a raw strain value through `MaxStrain`/`VisualStrain` into the
+/// normalized `0.0..=1.0` range a [`ColorRamp`] expects.
+pub fn strain_to_t(strain: f32, max_strain: f32, visual_strain: f32) -> f32 {
+    if max_strain <= 0.0 {
+        return 0.0;
+    }
+    ((strain / max_strain) * visual_strain).clamp(0.0, 1.0)
+}
+
+thread_local! {
+    static ACTIVE_RAMP: RefCell<ColorRamp> = RefCell::new(ColorRamp::default_ramp());
+}
+
+/// Replaces the active strain ramp with an arbitrary list of `(position, r,
+/// g, b)` stops, flattened to four floats per stop. Returns `false` without
+/// changing the active ramp if `stops` isn't a non-empty multiple of four
+/// floats, since this is a `#[wasm_bindgen]` entry point and JS can pass
+/// arbitrary data.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn set_strain_ramp(stops: Vec<f32>) -> bool {
+    if stops.is_empty() || !stops.len().is_multiple_of(4) {
+        return false;
+    }
+    let stops = stops
+        .chunks_exact(4)
+        .map(|chunk| ColorStop {
+            position: chunk[0],
+            rgb: [chunk[1], chunk[2], chunk[3]],
+        })
+        .collect();
+    match ColorRamp::new(stops) {
+        Some(ramp) => {
+            ACTIVE_RAMP.with(|active| *active.borrow_mut() = ramp);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn reset_strain_ramp() {
+    ACTIVE_RAMP.with(|ramp| *ramp.borrow_mut() = ColorRamp::default_ramp());
+}
+
+/// Samples the active strain ramp at `t` in `0.0..=1.0`.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn sample_strain_ramp(t: f32) -> Vec<f32> {
+    ACTIVE_RAMP.with(|ramp| ramp.borrow().sample(t).to_vec())
+}
+
+/// Maps a raw strain through `MaxStrain`/`VisualStrain` and samples the
+/// active ramp in one call, the shape the renderer needs per interval.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn strain_color(strain: f32, max_strain: f32, visual_strain: f32) -> Vec<f32> {
+    let t = strain_to_t(strain, max_strain, visual_strain);
+    ACTIVE_RAMP.with(|ramp| ramp.borrow().sample(t).to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_stop_ramp() -> ColorRamp {
+        ColorRamp::new(vec![
+            ColorStop { position: 0.0, rgb: [0.0, 0.0, 0.0] },
+            ColorStop { position: 1.0, rgb: [1.0, 1.0, 1.0] },
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn sample_clamps_outside_0_1() {
+        let ramp = two_stop_ramp();
+        assert_eq!(ramp.sample(-1.0), [0.0, 0.0, 0.0]);
+        assert_eq!(ramp.sample(2.0), [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn sample_interpolates_between_stops() {
+        let ramp = two_stop_ramp();
+        assert_eq!(ramp.sample(0.5), [0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn new_rejects_empty_stops() {
+        assert!(ColorRamp::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn new_sorts_out_of_order_stops() {
+        let ramp = ColorRamp::new(vec![
+            ColorStop { position: 1.0, rgb: [1.0, 0.0, 0.0] },
+            ColorStop { position: 0.0, rgb: [0.0, 1.0, 0.0] },
+        ])
+        .unwrap();
+        assert_eq!(ramp.sample(0.0), [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn strain_to_t_clamps_and_scales() {
+        assert_eq!(strain_to_t(0.05, 0.1, 1.0), 0.5);
+        assert_eq!(strain_to_t(1.0, 0.1, 1.0), 1.0);
+        assert_eq!(strain_to_t(1.0, 0.0, 1.0), 0.0);
+    }
+}
@@ -3,6 +3,7 @@
  * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
  */
 
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 pub const ATTENUATED_COLOR: [f32; 3] = [0.0, 0.0, 0.0];
@@ -39,7 +40,7 @@ pub const RAINBOW: [[f32; 3]; 12] = [
 pub const SHAPE_COUNT: usize = 16;
 pub const REST_SHAPE: u8 = 0;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
 pub enum Stage {
@@ -51,7 +52,7 @@ pub enum Stage {
     Realized,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SurfaceCharacter {
@@ -61,7 +62,7 @@ pub enum SurfaceCharacter {
     Bouncy,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum FabricFeature {
@@ -99,7 +100,7 @@ const CROSS1: f32 = 0.5_f32;
 const CROSS2: f32 = (PHI / 3_f32 - 1_f32 / 6_f32) * ROOT3;
 const CROSS3: f32 = PHI / 3_f32 * ROOT3 - 1_f32 + ROOT2 / ROOT3;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub fn default_fabric_feature(fabric_feature: FabricFeature) -> f32 {
     match fabric_feature {
         FabricFeature::Gravity => 0.0000001_f32,
@@ -131,7 +132,7 @@ pub fn default_fabric_feature(fabric_feature: FabricFeature) -> f32 {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum IntervalRole {
@@ -145,24 +146,3 @@ pub enum IntervalRole {
     BowEnd,
     FacePull,
 }
-
-#[wasm_bindgen]
-extern "C" {
-    // Use `js_namespace` here to bind `console.log(..)` instead of just
-    // `log(..)`
-    #[wasm_bindgen(js_namespace = console)]
-    pub fn log(s: &str);
-
-    // The `console.log` is quite polymorphic, so we can bind it with multiple
-    // signatures. Note that we need to use `js_name` to ensure we always call
-    // `log` in JS.
-    #[wasm_bindgen(js_namespace = console, js_name = log)]
-    pub fn log_f32(s: &str, a: f32);
-
-    // The `console.log` is quite polymorphic, so we can bind it with multiple
-    // signatures. Note that we need to use `js_name` to ensure we always call
-    // `log` in JS.
-    #[wasm_bindgen(js_namespace = console, js_name = log)]
-    pub fn log_u32(s: &str, a: u32);
-
-}
\ No newline at end of file
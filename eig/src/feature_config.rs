@@ -0,0 +1,209 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{default_fabric_feature, FabricFeature};
+
+pub const FEATURE_COUNT: usize = 24;
+
+const ALL_FEATURES: [FabricFeature; FEATURE_COUNT] = [
+    FabricFeature::Gravity,
+    FabricFeature::Drag,
+    FabricFeature::PretenstFactor,
+    FabricFeature::IterationsPerFrame,
+    FabricFeature::IntervalCountdown,
+    FabricFeature::RealizingCountdown,
+    FabricFeature::SlackThreshold,
+    FabricFeature::ShapingPretenstFactor,
+    FabricFeature::ShapingStiffnessFactor,
+    FabricFeature::ShapingDrag,
+    FabricFeature::MaxStrain,
+    FabricFeature::VisualStrain,
+    FabricFeature::NexusPushLength,
+    FabricFeature::ColumnPushLength,
+    FabricFeature::TriangleLength,
+    FabricFeature::RingLength,
+    FabricFeature::NexusCrossLength,
+    FabricFeature::ColumnCrossLength,
+    FabricFeature::BowMidLength,
+    FabricFeature::BowEndLength,
+    FabricFeature::PushOverPull,
+    FabricFeature::PushRadiusFactor,
+    FabricFeature::PullRadiusFactor,
+    FabricFeature::MaxStiffness,
+];
+
+/// Sensible min/max bounds and integer-ness for a `FabricFeature`, used to
+/// validate and clamp overrides in a [`FeatureConfig`].
+fn feature_bounds(feature: FabricFeature) -> (f32, f32, bool) {
+    match feature {
+        FabricFeature::Gravity => (0_f32, 0.00001_f32, false),
+        FabricFeature::Drag => (0_f32, 0.01_f32, false),
+        FabricFeature::PretenstFactor => (0_f32, 1_f32, false),
+        FabricFeature::IterationsPerFrame => (1_f32, 1000_f32, true),
+        FabricFeature::IntervalCountdown => (0_f32, 10000_f32, true),
+        FabricFeature::RealizingCountdown => (0_f32, 100000_f32, true),
+        FabricFeature::SlackThreshold => (0_f32, 0.01_f32, false),
+        FabricFeature::ShapingPretenstFactor => (0_f32, 1_f32, false),
+        FabricFeature::ShapingStiffnessFactor => (0_f32, 100_f32, false),
+        FabricFeature::ShapingDrag => (0_f32, 1_f32, false),
+        FabricFeature::MaxStrain => (0_f32, 1_f32, false),
+        FabricFeature::VisualStrain => (0_f32, 10_f32, false),
+        FabricFeature::NexusPushLength => (0.1_f32, 10_f32, false),
+        FabricFeature::ColumnPushLength => (0.1_f32, 10_f32, false),
+        FabricFeature::TriangleLength => (0.1_f32, 10_f32, false),
+        FabricFeature::RingLength => (0.1_f32, 10_f32, false),
+        FabricFeature::NexusCrossLength => (0.1_f32, 10_f32, false),
+        FabricFeature::ColumnCrossLength => (0.1_f32, 10_f32, false),
+        FabricFeature::BowMidLength => (0.01_f32, 1_f32, false),
+        FabricFeature::BowEndLength => (0.01_f32, 1_f32, false),
+        FabricFeature::PushOverPull => (0.1_f32, 10_f32, false),
+        FabricFeature::PushRadiusFactor => (0.1_f32, 20_f32, false),
+        FabricFeature::PullRadiusFactor => (0.1_f32, 20_f32, false),
+        FabricFeature::MaxStiffness => (0_f32, 0.01_f32, false),
+    }
+}
+
+/// One feature's current value plus its default, min/max and integer-ness.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FeatureEntry {
+    pub value: f32,
+    pub default: f32,
+    pub min: f32,
+    pub max: f32,
+    pub is_integer: bool,
+}
+
+impl FeatureEntry {
+    fn for_feature(feature: FabricFeature) -> FeatureEntry {
+        let default = default_fabric_feature(feature);
+        let (min, max, is_integer) = feature_bounds(feature);
+        FeatureEntry { value: default, default, min, max, is_integer }
+    }
+
+    fn clamp(&self, value: f32) -> f32 {
+        let clamped = value.clamp(self.min, self.max);
+        if self.is_integer {
+            clamped.round()
+        } else {
+            clamped
+        }
+    }
+}
+
+/// A full, overridable set of `FabricFeature` values, starting at the
+/// compiled-in defaults.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeatureConfig {
+    entries: [FeatureEntry; FEATURE_COUNT],
+}
+
+impl Default for FeatureConfig {
+    fn default() -> FeatureConfig {
+        let mut entries = [FeatureEntry::for_feature(FabricFeature::Gravity); FEATURE_COUNT];
+        for (index, feature) in ALL_FEATURES.iter().enumerate() {
+            entries[index] = FeatureEntry::for_feature(*feature);
+        }
+        FeatureConfig { entries }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl FeatureConfig {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> FeatureConfig {
+        FeatureConfig::default()
+    }
+
+    pub fn get(&self, feature: FabricFeature) -> f32 {
+        self.entries[feature as usize].value
+    }
+
+    pub fn entry(&self, feature: FabricFeature) -> FeatureEntry {
+        self.entries[feature as usize]
+    }
+
+    /// Validates and clamps `value` against the feature's bounds before
+    /// storing it.
+    pub fn set(&mut self, feature: FabricFeature, value: f32) {
+        let entry = &mut self.entries[feature as usize];
+        entry.value = entry.clamp(value);
+    }
+
+    pub fn reset(&mut self, feature: FabricFeature) {
+        let entry = &mut self.entries[feature as usize];
+        entry.value = entry.default;
+    }
+
+    pub fn reset_all(&mut self) {
+        *self = FeatureConfig::default();
+    }
+
+    /// Serializes the whole configuration to a compact JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("FeatureConfig always serializes")
+    }
+
+    /// Parses a configuration previously produced by `to_json`.
+    pub fn from_json(json: &str) -> Result<FeatureConfig, String> {
+        serde_json::from_str(json).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_starts_at_the_compiled_in_default() {
+        let config = FeatureConfig::new();
+        assert_eq!(config.get(FabricFeature::PretenstFactor), default_fabric_feature(FabricFeature::PretenstFactor));
+    }
+
+    #[test]
+    fn set_clamps_to_bounds() {
+        let mut config = FeatureConfig::new();
+        config.set(FabricFeature::PretenstFactor, 5.0);
+        assert_eq!(config.get(FabricFeature::PretenstFactor), 1.0);
+        config.set(FabricFeature::PretenstFactor, -5.0);
+        assert_eq!(config.get(FabricFeature::PretenstFactor), 0.0);
+    }
+
+    #[test]
+    fn set_rounds_integer_features() {
+        let mut config = FeatureConfig::new();
+        config.set(FabricFeature::IterationsPerFrame, 42.4);
+        assert_eq!(config.get(FabricFeature::IterationsPerFrame), 42.0);
+    }
+
+    #[test]
+    fn reset_reverts_to_default() {
+        let mut config = FeatureConfig::new();
+        let default = config.get(FabricFeature::MaxStrain);
+        config.set(FabricFeature::MaxStrain, default + 0.01);
+        config.reset(FabricFeature::MaxStrain);
+        assert_eq!(config.get(FabricFeature::MaxStrain), default);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let mut config = FeatureConfig::new();
+        config.set(FabricFeature::IterationsPerFrame, 250.0);
+        let json = config.to_json();
+        let restored = FeatureConfig::from_json(&json).unwrap();
+        assert_eq!(restored.get(FabricFeature::IterationsPerFrame), 250.0);
+    }
+
+    #[test]
+    fn from_json_rejects_garbage() {
+        assert!(FeatureConfig::from_json("not json").is_err());
+    }
+}
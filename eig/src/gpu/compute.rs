@@ -0,0 +1,302 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+use wgpu::util::DeviceExt;
+
+use crate::constants::{default_fabric_feature, FabricFeature, SurfaceCharacter};
+
+const SHADER_SOURCE: &str = include_str!("solver.wgsl");
+
+/// One interval's GPU-side inputs: the two joints it spans, its rest length
+/// (from `default_fabric_feature` for its `IntervalRole`, e.g.
+/// `NexusPushLength`/`TriangleLength`) and stiffness, and whether it pushes
+/// or pulls. Field order and size must match the WGSL `Interval` struct in
+/// `solver.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuInterval {
+    pub joint_a: u32,
+    pub joint_b: u32,
+    pub rest_length: f32,
+    pub stiffness: f32,
+    pub is_push: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    gravity: f32,
+    drag: f32,
+    pretenst_factor: f32,
+    joint_count: u32,
+    interval_count: u32,
+    surface_character: u32,
+    _padding: [u32; 2],
+}
+
+/// The initial state a [`GpuSolver`] is built from: joint positions plus
+/// the intervals connecting them.
+pub struct GpuFabricDescriptor<'a> {
+    pub joint_positions: &'a [[f32; 3]],
+    pub intervals: &'a [GpuInterval],
+    pub surface_character: SurfaceCharacter,
+}
+
+/// Runs force-integration steps for a fabric on the GPU.
+pub struct GpuSolver {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    clear_forces_pipeline: wgpu::ComputePipeline,
+    accumulate_forces_pipeline: wgpu::ComputePipeline,
+    integrate_pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    position_buffer: wgpu::Buffer,
+    position_readback_buffer: wgpu::Buffer,
+    strain_buffer: wgpu::Buffer,
+    strain_readback_buffer: wgpu::Buffer,
+    joint_count: u32,
+    interval_count: u32,
+}
+
+impl GpuSolver {
+    pub async fn new(descriptor: GpuFabricDescriptor<'_>) -> GpuSolver {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+            .expect("no suitable GPU adapter found");
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await
+            .expect("failed to create GPU device");
+
+        let joint_count = descriptor.joint_positions.len() as u32;
+        let interval_count = descriptor.intervals.len() as u32;
+
+        let positions: Vec<[f32; 4]> = descriptor
+            .joint_positions
+            .iter()
+            .map(|p| [p[0], p[1], p[2], 0.0])
+            .collect();
+
+        let uniforms = Uniforms {
+            gravity: default_fabric_feature(FabricFeature::Gravity),
+            drag: default_fabric_feature(FabricFeature::Drag),
+            pretenst_factor: default_fabric_feature(FabricFeature::PretenstFactor),
+            joint_count,
+            interval_count,
+            surface_character: descriptor.surface_character as u32,
+            _padding: [0; 2],
+        };
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("fabric-uniforms"),
+            contents: bytemuck::bytes_of(&uniforms),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let interval_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("fabric-intervals"),
+            contents: bytemuck::cast_slice(descriptor.intervals),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let position_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("fabric-positions"),
+            contents: bytemuck::cast_slice(&positions),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+        });
+        let velocity_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fabric-velocities"),
+            size: (joint_count.max(1) * std::mem::size_of::<[f32; 4]>() as u32) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        // Three fixed-point i32 atomics per joint (see solver.wgsl), not a
+        // vec3<f32>: WGSL has no float atomics, so force accumulation across
+        // intervals sharing a joint has to go through atomicAdd.
+        let force_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fabric-forces"),
+            size: (joint_count.max(1) * 3 * std::mem::size_of::<i32>() as u32) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let strain_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fabric-strains"),
+            size: (interval_count.max(1) * std::mem::size_of::<f32>() as u32) as u64,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let position_readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fabric-positions-readback"),
+            size: position_buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let strain_readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fabric-strains-readback"),
+            size: strain_buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fabric-solver"),
+            source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("fabric-solver-layout"),
+            entries: &[
+                storage_entry(0, true),
+                storage_entry(1, true),
+                storage_entry(2, false),
+                storage_entry(3, false),
+                storage_entry(4, false),
+                storage_entry(5, false),
+            ],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("fabric-solver-bind-group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: interval_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: position_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: velocity_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 4, resource: force_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 5, resource: strain_buffer.as_entire_binding() },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("fabric-solver-pipeline-layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let make_pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point,
+            })
+        };
+
+        GpuSolver {
+            clear_forces_pipeline: make_pipeline("clear_forces"),
+            accumulate_forces_pipeline: make_pipeline("accumulate_forces"),
+            integrate_pipeline: make_pipeline("integrate"),
+            device,
+            queue,
+            bind_group,
+            position_buffer,
+            position_readback_buffer,
+            strain_buffer,
+            strain_readback_buffer,
+            joint_count,
+            interval_count,
+        }
+    }
+
+    /// Dispatches `iterations_per_frame` integration steps, defaulting to
+    /// `default_fabric_feature(FabricFeature::IterationsPerFrame)`.
+    pub fn step(&self, iterations_per_frame: Option<u32>) {
+        let iterations =
+            iterations_per_frame.unwrap_or_else(|| default_fabric_feature(FabricFeature::IterationsPerFrame) as u32);
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("fabric-step") });
+        for _ in 0..iterations {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_bind_group(0, &self.bind_group, &[]);
+
+            pass.set_pipeline(&self.clear_forces_pipeline);
+            pass.dispatch_workgroups(workgroup_count(self.joint_count), 1, 1);
+
+            pass.set_pipeline(&self.accumulate_forces_pipeline);
+            pass.dispatch_workgroups(workgroup_count(self.interval_count), 1, 1);
+
+            pass.set_pipeline(&self.integrate_pipeline);
+            pass.dispatch_workgroups(workgroup_count(self.joint_count), 1, 1);
+        }
+        self.queue.submit(Some(encoder.finish()));
+    }
+
+    /// Reads the current joint positions back from the GPU.
+    pub async fn read_positions(&self) -> Vec<[f32; 3]> {
+        let data = self
+            .copy_and_map(&self.position_buffer, &self.position_readback_buffer)
+            .await;
+        let positions = bytemuck::cast_slice::<u8, [f32; 4]>(&data)
+            .iter()
+            .map(|p| [p[0], p[1], p[2]])
+            .collect();
+        drop(data);
+        self.position_readback_buffer.unmap();
+        positions
+    }
+
+    /// Reads the per-interval strains computed by `accumulate_forces` back
+    /// from the GPU, e.g. for strain-driven coloring.
+    pub async fn read_strains(&self) -> Vec<f32> {
+        let data = self
+            .copy_and_map(&self.strain_buffer, &self.strain_readback_buffer)
+            .await;
+        let strains = bytemuck::cast_slice::<u8, f32>(&data).to_vec();
+        drop(data);
+        self.strain_readback_buffer.unmap();
+        strains
+    }
+
+    async fn copy_and_map<'a>(
+        &'a self,
+        source: &wgpu::Buffer,
+        readback: &'a wgpu::Buffer,
+    ) -> wgpu::BufferView<'a> {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("fabric-readback") });
+        encoder.copy_buffer_to_buffer(source, 0, readback, 0, source.size());
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = futures_intrusive::channel::shared::oneshot_channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            sender.send(result).expect("readback channel closed");
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .receive()
+            .await
+            .expect("readback never completed")
+            .expect("failed to map buffer");
+
+        slice.get_mapped_range()
+    }
+}
+
+fn storage_entry(binding: u32, read_only: bool) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: if binding == 0 {
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            }
+        } else {
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            }
+        },
+        count: None,
+    }
+}
+
+fn workgroup_count(element_count: u32) -> u32 {
+    element_count.div_ceil(64).max(1)
+}
@@ -0,0 +1,11 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+//! GPU compute backend for per-frame fabric integration, gated behind the
+//! `gpu` feature.
+
+mod compute;
+
+pub use compute::{GpuFabricDescriptor, GpuInterval, GpuSolver};
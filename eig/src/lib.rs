@@ -0,0 +1,22 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+pub mod color_ramp;
+pub mod constants;
+pub mod feature_config;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod logger;
+pub mod palette;
+pub mod pbr;
+
+pub use color_ramp::*;
+pub use constants::*;
+pub use feature_config::*;
+#[cfg(feature = "gpu")]
+pub use gpu::*;
+pub use logger::*;
+pub use palette::*;
+pub use pbr::*;
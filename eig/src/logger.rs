@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+pub trait Logger {
+    fn log(&self, message: &str);
+    fn log_f32(&self, message: &str, value: f32);
+    fn log_u32(&self, message: &str, value: u32);
+}
+
+/// Logs to `console.log`.
+#[cfg(feature = "wasm")]
+pub struct ConsoleLogger;
+
+#[cfg(feature = "wasm")]
+mod console {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = console, js_name = log)]
+        pub fn log(s: &str);
+
+        #[wasm_bindgen(js_namespace = console, js_name = log)]
+        pub fn log_f32(s: &str, a: f32);
+
+        #[wasm_bindgen(js_namespace = console, js_name = log)]
+        pub fn log_u32(s: &str, a: u32);
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Logger for ConsoleLogger {
+    fn log(&self, message: &str) {
+        console::log(message);
+    }
+
+    fn log_f32(&self, message: &str, value: f32) {
+        console::log_f32(message, value);
+    }
+
+    fn log_u32(&self, message: &str, value: u32) {
+        console::log_u32(message, value);
+    }
+}
+
+/// Logs to stdout.
+#[cfg(not(feature = "wasm"))]
+pub struct StdoutLogger;
+
+#[cfg(not(feature = "wasm"))]
+impl Logger for StdoutLogger {
+    fn log(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn log_f32(&self, message: &str, value: f32) {
+        println!("{} {}", message, value);
+    }
+
+    fn log_u32(&self, message: &str, value: u32) {
+        println!("{} {}", message, value);
+    }
+}
+
+/// Discards everything.
+pub struct NoOpLogger;
+
+impl Logger for NoOpLogger {
+    fn log(&self, _message: &str) {}
+    fn log_f32(&self, _message: &str, _value: f32) {}
+    fn log_u32(&self, _message: &str, _value: u32) {}
+}
+
+#[cfg(feature = "wasm")]
+pub fn default_logger() -> ConsoleLogger {
+    ConsoleLogger
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn default_logger() -> StdoutLogger {
+    StdoutLogger
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_op_logger_does_not_panic() {
+        let logger = NoOpLogger;
+        logger.log("hello");
+        logger.log_f32("value", 1.5);
+        logger.log_u32("count", 3);
+    }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn default_logger_is_stdout_natively() {
+        let logger: StdoutLogger = default_logger();
+        logger.log("native default logger");
+    }
+}
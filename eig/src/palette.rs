@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::constants::{IntervalRole, ATTENUATED_COLOR, ROLE_COLORS, SLACK_COLOR};
+
+/// One theme's colors: an RGB entry per `IntervalRole`, plus the slack and
+/// attenuated colors. `default_theme` holds `ROLE_COLORS`, `SLACK_COLOR` and
+/// `ATTENUATED_COLOR`.
+#[derive(Clone)]
+pub struct Palette {
+    pub role_colors: [[f32; 3]; 9],
+    pub slack_color: [f32; 3],
+    pub attenuated_color: [f32; 3],
+}
+
+impl Palette {
+    pub fn default_theme() -> Palette {
+        Palette {
+            role_colors: ROLE_COLORS,
+            slack_color: SLACK_COLOR,
+            attenuated_color: ATTENUATED_COLOR,
+        }
+    }
+
+    pub fn storm_theme() -> Palette {
+        Palette {
+            role_colors: [
+                [0.231, 0.298, 0.592],
+                [0.384, 0.290, 0.592],
+                [0.149, 0.439, 0.533],
+                [0.290, 0.184, 0.482],
+                [0.349, 0.412, 0.498],
+                [0.200, 0.482, 0.431],
+                [0.322, 0.400, 0.463],
+                [0.373, 0.412, 0.471],
+                [0.408, 0.408, 0.439],
+            ],
+            slack_color: [0.349, 0.494, 0.890],
+            attenuated_color: [0.043, 0.051, 0.094],
+        }
+    }
+
+    pub fn dawn_theme() -> Palette {
+        Palette {
+            role_colors: [
+                [0.980, 0.741, 0.369],
+                [0.988, 0.518, 0.384],
+                [0.459, 0.792, 0.827],
+                [0.808, 0.486, 0.898],
+                [0.898, 0.820, 0.561],
+                [0.506, 0.898, 0.588],
+                [0.816, 0.851, 0.569],
+                [0.914, 0.886, 0.345],
+                [0.761, 0.761, 0.761],
+            ],
+            slack_color: [0.522, 0.910, 0.486],
+            attenuated_color: [0.988, 0.988, 0.949],
+        }
+    }
+
+    pub(crate) fn role_color(&self, role: IntervalRole) -> [f32; 3] {
+        self.role_colors[role as usize]
+    }
+}
+
+thread_local! {
+    static THEMES: RefCell<HashMap<String, Palette>> = RefCell::new({
+        let mut themes = HashMap::new();
+        themes.insert("default".to_string(), Palette::default_theme());
+        themes.insert("storm".to_string(), Palette::storm_theme());
+        themes.insert("dawn".to_string(), Palette::dawn_theme());
+        themes
+    });
+    static ACTIVE_THEME: RefCell<String> = RefCell::new("default".to_string());
+}
+
+pub(crate) fn with_active_palette<T>(f: impl FnOnce(&Palette) -> T) -> T {
+    ACTIVE_THEME.with(|active| {
+        THEMES.with(|themes| {
+            let themes = themes.borrow();
+            let name = active.borrow();
+            let palette = themes
+                .get(name.as_str())
+                .or_else(|| themes.get("default"))
+                .expect("default theme is always registered");
+            f(palette)
+        })
+    })
+}
+
+/// Installs a named theme, overwriting any existing theme of the same name.
+/// Returns `false` without installing anything if the color vectors are the
+/// wrong length, since this is a `#[wasm_bindgen]` entry point and JS can
+/// pass arbitrary data.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn install_theme(
+    name: &str,
+    role_colors: Vec<f32>,
+    slack_color: Vec<f32>,
+    attenuated_color: Vec<f32>,
+) -> bool {
+    if role_colors.len() != 27 || slack_color.len() != 3 || attenuated_color.len() != 3 {
+        return false;
+    }
+    let mut roles = [[0_f32; 3]; 9];
+    for (index, chunk) in role_colors.chunks_exact(3).enumerate() {
+        roles[index] = [chunk[0], chunk[1], chunk[2]];
+    }
+    let palette = Palette {
+        role_colors: roles,
+        slack_color: [slack_color[0], slack_color[1], slack_color[2]],
+        attenuated_color: [
+            attenuated_color[0],
+            attenuated_color[1],
+            attenuated_color[2],
+        ],
+    };
+    THEMES.with(|themes| themes.borrow_mut().insert(name.to_string(), palette));
+    true
+}
+
+/// Selects which installed theme subsequent color queries read from. Falls
+/// back to "default" and returns `false` if `name` is not a known theme.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn set_active_theme(name: &str) -> bool {
+    let known = THEMES.with(|themes| themes.borrow().contains_key(name));
+    ACTIVE_THEME.with(|active| {
+        *active.borrow_mut() = if known { name.to_string() } else { "default".to_string() };
+    });
+    known
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn active_theme() -> String {
+    ACTIVE_THEME.with(|active| active.borrow().clone())
+}
+
+/// RGB color for a role under the currently active theme.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn role_color(role: IntervalRole) -> Vec<f32> {
+    with_active_palette(|palette| palette.role_color(role).to_vec())
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn slack_color() -> Vec<f32> {
+    with_active_palette(|palette| palette.slack_color.to_vec())
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn attenuated_color() -> Vec<f32> {
+    with_active_palette(|palette| palette.attenuated_color.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_theme_rejects_wrong_length_role_colors() {
+        assert!(!install_theme("bad", vec![0.0; 26], vec![0.0; 3], vec![0.0; 3]));
+    }
+
+    #[test]
+    fn install_theme_rejects_wrong_length_slack_color() {
+        assert!(!install_theme("bad", vec![0.0; 27], vec![0.0; 2], vec![0.0; 3]));
+    }
+
+    #[test]
+    fn install_theme_rejects_wrong_length_attenuated_color() {
+        assert!(!install_theme("bad", vec![0.0; 27], vec![0.0; 3], vec![0.0; 4]));
+    }
+
+    #[test]
+    fn set_active_theme_resets_to_default_for_unknown_name() {
+        assert!(set_active_theme("storm"));
+        assert!(!set_active_theme("no-such-theme"));
+        assert_eq!(active_theme(), "default");
+    }
+
+    #[test]
+    fn queries_read_from_the_newly_activated_theme() {
+        let role_colors: Vec<f32> = (0..9).flat_map(|_| [0.1_f32, 0.2, 0.3]).collect();
+        install_theme("test-theme", role_colors, vec![0.4, 0.5, 0.6], vec![0.7, 0.8, 0.9]);
+
+        assert!(set_active_theme("test-theme"));
+        assert_eq!(active_theme(), "test-theme");
+        assert_eq!(role_color(IntervalRole::NexusPush), vec![0.1, 0.2, 0.3]);
+        assert_eq!(slack_color(), vec![0.4, 0.5, 0.6]);
+        assert_eq!(attenuated_color(), vec![0.7, 0.8, 0.9]);
+
+        set_active_theme("default");
+    }
+}
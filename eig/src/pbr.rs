@@ -0,0 +1,149 @@
+/*
+ * Copyright (c) 2020. Beautiful Code BV, Rotterdam, Netherlands
+ * Licensed under GNU GENERAL PUBLIC LICENSE Version 3.
+ */
+
+//! PBR-ready geometry and shading data for push/pull intervals.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::color_ramp::strain_to_t;
+use crate::constants::{default_fabric_feature, FabricFeature, IntervalRole};
+use crate::palette::with_active_palette;
+
+/// Tube radius is `BASE_RADIUS` scaled by the interval's role-appropriate
+/// radius factor, so a push (thicker) reads differently from a pull
+/// (thinner) at a glance.
+const BASE_RADIUS: f32 = 0.01;
+
+/// Metallic/roughness pair for rigid compression struts (pushes).
+const PUSH_METALLIC: f32 = 0.8;
+const PUSH_ROUGHNESS: f32 = 0.35;
+
+/// Metallic/roughness pair for tensile cables (pulls).
+const PULL_METALLIC: f32 = 0.1;
+const PULL_ROUGHNESS: f32 = 0.6;
+
+const EMISSIVE_INTENSITY: f32 = 0.6;
+
+/// A tube-geometry descriptor for one interval: radius from the
+/// appropriate `PushRadiusFactor`/`PullRadiusFactor` feature, length from
+/// current state.
+#[derive(Clone, Copy, Debug)]
+pub struct TubeGeometry {
+    pub radius: f32,
+    pub length: f32,
+}
+
+/// A minimal PBR material: base color from the role palette, a
+/// metallic/roughness pair, and an emissive term driven by `VisualStrain`.
+#[derive(Clone, Copy, Debug)]
+pub struct Material {
+    pub base_color: [f32; 3],
+    pub metallic: f32,
+    pub roughness: f32,
+    pub emissive: f32,
+}
+
+pub fn tube_geometry(is_push: bool, length: f32) -> TubeGeometry {
+    let radius_factor = default_fabric_feature(if is_push {
+        FabricFeature::PushRadiusFactor
+    } else {
+        FabricFeature::PullRadiusFactor
+    });
+    TubeGeometry { radius: BASE_RADIUS * radius_factor, length }
+}
+
+pub fn material(role: IntervalRole, is_push: bool, strain: f32, max_strain: f32, visual_strain: f32) -> Material {
+    let base_color = with_active_palette(|palette| palette.role_color(role));
+    let (metallic, roughness) = if is_push {
+        (PUSH_METALLIC, PUSH_ROUGHNESS)
+    } else {
+        (PULL_METALLIC, PULL_ROUGHNESS)
+    };
+    let emissive = strain_to_t(strain, max_strain, visual_strain) * EMISSIVE_INTENSITY;
+    Material { base_color, metallic, roughness, emissive }
+}
+
+fn role_from_u8(value: u8) -> Result<IntervalRole, String> {
+    match value {
+        0 => Ok(IntervalRole::NexusPush),
+        1 => Ok(IntervalRole::ColumnPush),
+        2 => Ok(IntervalRole::Triangle),
+        3 => Ok(IntervalRole::Ring),
+        4 => Ok(IntervalRole::NexusCross),
+        5 => Ok(IntervalRole::ColumnCross),
+        6 => Ok(IntervalRole::BowMid),
+        7 => Ok(IntervalRole::BowEnd),
+        8 => Ok(IntervalRole::FacePull),
+        _ => Err(format!("{} is not a valid IntervalRole", value)),
+    }
+}
+
+/// Computes geometry and material data for every interval and flattens it
+/// into one `[radius, length, r, g, b, metallic, roughness, emissive]`
+/// group per interval. Returns an error instead of panicking if the input
+/// vectors (a `#[wasm_bindgen]` entry point receiving arbitrary JS data)
+/// don't all have the same length.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn interval_pbr_data(
+    roles: Vec<u8>,
+    is_push: Vec<u8>,
+    lengths: Vec<f32>,
+    strains: Vec<f32>,
+    max_strain: f32,
+    visual_strain: f32,
+) -> Result<Vec<f32>, String> {
+    let count = roles.len();
+    if is_push.len() != count || lengths.len() != count || strains.len() != count {
+        return Err("roles, is_push, lengths and strains must all have the same length".to_string());
+    }
+
+    let mut data = Vec::with_capacity(count * 8);
+    for index in 0..count {
+        let push = is_push[index] != 0;
+        let geometry = tube_geometry(push, lengths[index]);
+        let mat = material(role_from_u8(roles[index])?, push, strains[index], max_strain, visual_strain);
+        data.push(geometry.radius);
+        data.push(geometry.length);
+        data.push(mat.base_color[0]);
+        data.push(mat.base_color[1]);
+        data.push(mat.base_color[2]);
+        data.push(mat.metallic);
+        data.push(mat.roughness);
+        data.push(mat.emissive);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tube_geometry_scales_radius_by_role_factor() {
+        let push = tube_geometry(true, 2.0);
+        let pull = tube_geometry(false, 2.0);
+        assert_eq!(push.length, 2.0);
+        assert!(push.radius > pull.radius);
+    }
+
+    #[test]
+    fn interval_pbr_data_rejects_mismatched_lengths() {
+        let result = interval_pbr_data(vec![0, 1], vec![1], vec![1.0, 1.0], vec![0.0, 0.0], 0.1, 1.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interval_pbr_data_rejects_out_of_range_role() {
+        let result = interval_pbr_data(vec![9], vec![1], vec![1.0], vec![0.0], 0.1, 1.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn interval_pbr_data_emits_one_group_per_interval() {
+        let result = interval_pbr_data(vec![0, 8], vec![1, 0], vec![1.0, 1.5], vec![0.0, 0.05], 0.1, 1.0).unwrap();
+        assert_eq!(result.len(), 2 * 8);
+    }
+}